@@ -1,5 +1,8 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Error};
+use std::ops::{Add, Mul, Sub};
 use std::result::Result;
+use std::str::FromStr;
 
 pub trait Color {
     type Type;
@@ -31,6 +34,52 @@ impl RgbaColorType {
             a: 1.0,
         }
     }
+
+    pub fn new_with_alpha(r: f64, g: f64, b: f64, a: f64) -> RgbaColorType {
+        RgbaColorType { r, g, b, a }
+    }
+
+    pub fn with_alpha(&self, a: f64) -> RgbaColorType {
+        RgbaColorType { a, ..*self }
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.a
+    }
+
+    pub fn without_alpha(&self) -> RgbColorType {
+        RgbColorType::new(self.r, self.g, self.b)
+    }
+}
+
+/// A 3-channel color with no alpha, for callers who don't need transparency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RgbColorType {
+    r: f64,
+    g: f64,
+    b: f64,
+}
+
+impl RgbColorType {
+    pub fn new(r: f64, g: f64, b: f64) -> RgbColorType {
+        RgbColorType { r, g, b }
+    }
+
+    pub fn with_alpha(&self, a: f64) -> RgbaColorType {
+        RgbaColorType::new_with_alpha(self.r, self.g, self.b, a)
+    }
+}
+
+impl From<RgbColorType> for RgbaColorType {
+    fn from(c: RgbColorType) -> RgbaColorType {
+        RgbaColorType::new(c.r, c.g, c.b)
+    }
+}
+
+impl From<RgbaColorType> for RgbColorType {
+    fn from(c: RgbaColorType) -> RgbColorType {
+        c.without_alpha()
+    }
 }
 
 impl HslaColorType {
@@ -129,7 +178,7 @@ impl From<HslaColorType> for RgbaColorType {
             g = hue2rgb(p, q, h);
             b = hue2rgb(p, q, h - 1.0 / 3.0);
         }
-        RgbaColorType::new(r, g, b)
+        RgbaColorType::new_with_alpha(r, g, b, c.a)
     }
 }
 impl From<RgbaColorType> for HslaColorType {
@@ -174,9 +223,136 @@ impl From<RgbaColorType> for HslaColorType {
         s = 100.0 * s;
         l = 100.0 * l;
 
-        HslaColorType::new(h, s, l)
+        let mut hsl = HslaColorType { h, s, l, a: c.a };
+        hsl.validate();
+        hsl
+    }
+}
+impl RgbaColorType {
+    pub fn to_hex(&self) -> String {
+        let [r, g, b]: [u8; 3] = (*self).into();
+        let a = (self.a * 255.0).round() as u8;
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseColorError {
+    message: String,
+}
+
+impl ParseColorError {
+    fn new(message: &str) -> ParseColorError {
+        ParseColorError {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        fmt.write_str(self.message.as_str())
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+fn hex_byte(hex: &str, i: usize) -> Result<u8, ParseColorError> {
+    let byte = hex
+        .get(i..i + 2)
+        .ok_or_else(|| ParseColorError::new("invalid hex digit"))?;
+    u8::from_str_radix(byte, 16).map_err(|_| ParseColorError::new("invalid hex digit"))
+}
+
+fn hex_nibble(c: char) -> Result<u8, ParseColorError> {
+    let doubled: String = std::iter::repeat_n(c, 2).collect();
+    u8::from_str_radix(&doubled, 16).map_err(|_| ParseColorError::new("invalid hex digit"))
+}
+
+impl FromStr for RgbaColorType {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<RgbaColorType, ParseColorError> {
+        let s = s.trim();
+
+        if let Some(hex) = s.strip_prefix('#') {
+            let (r, g, b, a) = match hex.len() {
+                3 => {
+                    let chars: Vec<char> = hex.chars().collect();
+                    (
+                        hex_nibble(chars[0])?,
+                        hex_nibble(chars[1])?,
+                        hex_nibble(chars[2])?,
+                        255u8,
+                    )
+                }
+                6 => (hex_byte(hex, 0)?, hex_byte(hex, 2)?, hex_byte(hex, 4)?, 255u8),
+                8 => (
+                    hex_byte(hex, 0)?,
+                    hex_byte(hex, 2)?,
+                    hex_byte(hex, 4)?,
+                    hex_byte(hex, 6)?,
+                ),
+                _ => return Err(ParseColorError::new("hex color must be 3, 6 or 8 digits")),
+            };
+            let mut c = RgbaColorType::from([r, g, b]);
+            c.a = a as f64 / 255.0;
+            return Ok(c);
+        }
+
+        let lower = s.to_lowercase();
+        if lower.starts_with("rgba") || lower.starts_with("rgb") {
+            let open = lower
+                .find('(')
+                .ok_or_else(|| ParseColorError::new("missing ( in rgb() form"))?;
+            let close = lower
+                .rfind(')')
+                .ok_or_else(|| ParseColorError::new("missing ) in rgb() form"))?;
+            let inner = s
+                .get(open + 1..close)
+                .ok_or_else(|| ParseColorError::new("malformed rgb()/rgba() form"))?;
+            let parts: Vec<&str> = inner.split(',').map(|p| p.trim()).collect();
+
+            if lower.starts_with("rgba") {
+                if parts.len() != 4 {
+                    return Err(ParseColorError::new("rgba() requires 4 components"));
+                }
+                let r: u8 = parts[0]
+                    .parse()
+                    .map_err(|_| ParseColorError::new("invalid red component"))?;
+                let g: u8 = parts[1]
+                    .parse()
+                    .map_err(|_| ParseColorError::new("invalid green component"))?;
+                let b: u8 = parts[2]
+                    .parse()
+                    .map_err(|_| ParseColorError::new("invalid blue component"))?;
+                let a: f64 = parts[3]
+                    .parse()
+                    .map_err(|_| ParseColorError::new("invalid alpha component"))?;
+                let mut c = RgbaColorType::from([r, g, b]);
+                c.a = a;
+                return Ok(c);
+            }
+
+            if parts.len() != 3 {
+                return Err(ParseColorError::new("rgb() requires 3 components"));
+            }
+            let r: u8 = parts[0]
+                .parse()
+                .map_err(|_| ParseColorError::new("invalid red component"))?;
+            let g: u8 = parts[1]
+                .parse()
+                .map_err(|_| ParseColorError::new("invalid green component"))?;
+            let b: u8 = parts[2]
+                .parse()
+                .map_err(|_| ParseColorError::new("invalid blue component"))?;
+            return Ok(RgbaColorType::from([r, g, b]));
+        }
+
+        Err(ParseColorError::new("unrecognized color format"))
     }
 }
+
 impl Display for RgbaColorType {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
         fmt.write_str(format!("RGB({:?}, {:?}, {:?})", self.r, self.g, self.b).as_str())
@@ -190,6 +366,442 @@ impl Color for RgbaColorType {
     }
 }
 
+impl RgbaColorType {
+    /// Moves lightness a fraction of the way towards 100%.
+    pub fn lighten(&self, amount: f64) -> RgbaColorType {
+        let mut hsl: HslaColorType = (*self).into();
+        hsl.l += amount * (100.0 - hsl.l);
+        hsl.validate();
+        hsl.into()
+    }
+
+    /// Moves lightness a fraction of the way towards 0%.
+    pub fn darken(&self, amount: f64) -> RgbaColorType {
+        let mut hsl: HslaColorType = (*self).into();
+        hsl.l -= amount * hsl.l;
+        hsl.validate();
+        hsl.into()
+    }
+
+    /// Moves saturation a fraction of the way towards 100%.
+    pub fn saturate(&self, amount: f64) -> RgbaColorType {
+        let mut hsl: HslaColorType = (*self).into();
+        hsl.s += amount * (100.0 - hsl.s);
+        hsl.validate();
+        hsl.into()
+    }
+
+    /// Moves saturation a fraction of the way towards 0%.
+    pub fn desaturate(&self, amount: f64) -> RgbaColorType {
+        let mut hsl: HslaColorType = (*self).into();
+        hsl.s -= amount * hsl.s;
+        hsl.validate();
+        hsl.into()
+    }
+
+    /// Rotates the hue by the given number of degrees, wrapping around 360.
+    pub fn rotate_hue(&self, degrees: f64) -> RgbaColorType {
+        let mut hsl: HslaColorType = (*self).into();
+        hsl.h += degrees;
+        hsl.validate();
+        hsl.into()
+    }
+}
+
+impl RgbaColorType {
+    /// Interpolates every channel, including alpha, towards `other` by `t`,
+    /// clamping `t` to `0.0..1.0`.
+    pub fn lerp(&self, other: &RgbaColorType, t: f64) -> RgbaColorType {
+        let t = t.clamp(0.0, 1.0);
+        RgbaColorType {
+            r: (1.0 - t) * self.r + t * other.r,
+            g: (1.0 - t) * self.g + t * other.g,
+            b: (1.0 - t) * self.b + t * other.b,
+            a: (1.0 - t) * self.a + t * other.a,
+        }
+    }
+}
+
+/// An ordered set of color stops that can be sampled at any point along
+/// `0.0..1.0`, interpolating between the two bracketing stops.
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    stops: Vec<(f64, RgbaColorType)>,
+}
+
+impl Gradient {
+    pub fn new(mut stops: Vec<(f64, RgbaColorType)>) -> Gradient {
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        Gradient { stops }
+    }
+
+    /// Returns `None` if the gradient has no stops; otherwise lerps between
+    /// the two stops bracketing `t` (clamped to `0.0..1.0`).
+    pub fn sample(&self, t: f64) -> Option<RgbaColorType> {
+        if self.stops.is_empty() {
+            return None;
+        }
+        let t = t.clamp(0.0, 1.0);
+
+        if self.stops.len() == 1 || t <= self.stops[0].0 {
+            return Some(self.stops[0].1);
+        }
+        let last = self.stops[self.stops.len() - 1];
+        if t >= last.0 {
+            return Some(last.1);
+        }
+
+        for window in self.stops.windows(2) {
+            let (p0, c0) = window[0];
+            let (p1, c1) = window[1];
+            if t >= p0 && t <= p1 {
+                let local_t = if p1 > p0 { (t - p0) / (p1 - p0) } else { 0.0 };
+                return Some(c0.lerp(&c1, local_t));
+            }
+        }
+        Some(last.1)
+    }
+
+    /// Returns an iterator yielding `n` evenly spaced colors across the gradient;
+    /// it yields nothing if the gradient has no stops.
+    pub fn take(&self, n: usize) -> GradientIter<'_> {
+        GradientIter {
+            gradient: self,
+            n,
+            i: 0,
+        }
+    }
+}
+
+pub struct GradientIter<'a> {
+    gradient: &'a Gradient,
+    n: usize,
+    i: usize,
+}
+
+impl<'a> Iterator for GradientIter<'a> {
+    type Item = RgbaColorType;
+
+    fn next(&mut self) -> Option<RgbaColorType> {
+        if self.i >= self.n {
+            return None;
+        }
+        let t = if self.n == 1 {
+            0.0
+        } else {
+            self.i as f64 / (self.n - 1) as f64
+        };
+        self.i += 1;
+        self.gradient.sample(t)
+    }
+}
+
+impl Add for RgbaColorType {
+    type Output = RgbaColorType;
+
+    fn add(self, other: RgbaColorType) -> RgbaColorType {
+        RgbaColorType {
+            r: (self.r + other.r).clamp(0.0, 1.0),
+            g: (self.g + other.g).clamp(0.0, 1.0),
+            b: (self.b + other.b).clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+}
+
+impl Sub for RgbaColorType {
+    type Output = RgbaColorType;
+
+    fn sub(self, other: RgbaColorType) -> RgbaColorType {
+        RgbaColorType {
+            r: (self.r - other.r).clamp(0.0, 1.0),
+            g: (self.g - other.g).clamp(0.0, 1.0),
+            b: (self.b - other.b).clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+}
+
+impl Mul<f64> for RgbaColorType {
+    type Output = RgbaColorType;
+
+    fn mul(self, scalar: f64) -> RgbaColorType {
+        RgbaColorType {
+            r: (self.r * scalar).clamp(0.0, 1.0),
+            g: (self.g * scalar).clamp(0.0, 1.0),
+            b: (self.b * scalar).clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+}
+
+impl RgbaColorType {
+    /// Applies `f` to the r/g/b channels, clamping each result to `0.0..1.0`.
+    pub fn map<F: Fn(f64) -> f64>(&self, f: F) -> RgbaColorType {
+        RgbaColorType {
+            r: f(self.r).clamp(0.0, 1.0),
+            g: f(self.g).clamp(0.0, 1.0),
+            b: f(self.b).clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+}
+
+/// CIE 1931 XYZ color space, relative to the D65 white point and scaled 0..100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct XyzColorType {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+/// CIELAB color space, useful for perceptually-uniform comparisons.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabColorType {
+    l: f64,
+    a: f64,
+    b: f64,
+}
+
+const D65_XN: f64 = 95.047;
+const D65_YN: f64 = 100.0;
+const D65_ZN: f64 = 108.883;
+
+fn srgb_linearize(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn srgb_delinearize(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn lab_f(t: f64) -> f64 {
+    if t > 216.0 / 24389.0 {
+        t.cbrt()
+    } else {
+        (903.3 * t + 16.0) / 116.0
+    }
+}
+
+fn lab_f_inv(t: f64) -> f64 {
+    let t3 = t.powi(3);
+    if t3 > 216.0 / 24389.0 {
+        t3
+    } else {
+        (116.0 * t - 16.0) / 903.3
+    }
+}
+
+impl From<RgbaColorType> for XyzColorType {
+    fn from(c: RgbaColorType) -> XyzColorType {
+        let r = srgb_linearize(c.r);
+        let g = srgb_linearize(c.g);
+        let b = srgb_linearize(c.b);
+
+        XyzColorType {
+            x: (0.4124 * r + 0.3576 * g + 0.1805 * b) * 100.0,
+            y: (0.2126 * r + 0.7152 * g + 0.0722 * b) * 100.0,
+            z: (0.0193 * r + 0.1192 * g + 0.9505 * b) * 100.0,
+        }
+    }
+}
+
+impl From<XyzColorType> for RgbaColorType {
+    fn from(c: XyzColorType) -> RgbaColorType {
+        let x = c.x / 100.0;
+        let y = c.y / 100.0;
+        let z = c.z / 100.0;
+
+        let r = 3.2406 * x - 1.5372 * y - 0.4986 * z;
+        let g = -0.9689 * x + 1.8758 * y + 0.0415 * z;
+        let b = 0.0557 * x - 0.2040 * y + 1.0570 * z;
+
+        RgbaColorType::new(
+            srgb_delinearize(r).clamp(0.0, 1.0),
+            srgb_delinearize(g).clamp(0.0, 1.0),
+            srgb_delinearize(b).clamp(0.0, 1.0),
+        )
+    }
+}
+
+impl From<XyzColorType> for LabColorType {
+    fn from(c: XyzColorType) -> LabColorType {
+        let fx = lab_f(c.x / D65_XN);
+        let fy = lab_f(c.y / D65_YN);
+        let fz = lab_f(c.z / D65_ZN);
+
+        LabColorType {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl From<LabColorType> for XyzColorType {
+    fn from(c: LabColorType) -> XyzColorType {
+        let fy = (c.l + 16.0) / 116.0;
+        let fx = fy + c.a / 500.0;
+        let fz = fy - c.b / 200.0;
+
+        XyzColorType {
+            x: lab_f_inv(fx) * D65_XN,
+            y: lab_f_inv(fy) * D65_YN,
+            z: lab_f_inv(fz) * D65_ZN,
+        }
+    }
+}
+
+impl From<RgbaColorType> for LabColorType {
+    fn from(c: RgbaColorType) -> LabColorType {
+        let xyz: XyzColorType = c.into();
+        xyz.into()
+    }
+}
+
+impl From<LabColorType> for RgbaColorType {
+    fn from(c: LabColorType) -> RgbaColorType {
+        let xyz: XyzColorType = c.into();
+        xyz.into()
+    }
+}
+
+impl LabColorType {
+    /// Euclidean distance in Lab space; a simple, widely used measure of
+    /// perceptual color difference.
+    pub fn delta_e76(&self, other: &LabColorType) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+}
+
+/// Determines how channels are laid out inside a `PackedColor`'s `u32`.
+pub trait PackedOrder {
+    fn pack(r: u8, g: u8, b: u8, a: u8) -> u32;
+    fn unpack(data: u32) -> (u8, u8, u8, u8);
+}
+
+/// `0xRRGGBBAA` channel order.
+pub struct Rgba;
+
+/// `0x00RRGGBB` channel order (alpha is dropped on pack, assumed opaque on unpack).
+pub struct Zrgb;
+
+impl PackedOrder for Rgba {
+    fn pack(r: u8, g: u8, b: u8, a: u8) -> u32 {
+        ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | (a as u32)
+    }
+
+    fn unpack(data: u32) -> (u8, u8, u8, u8) {
+        let r = ((data & 0xFF00_0000) >> 24) as u8;
+        let g = ((data & 0x00FF_0000) >> 16) as u8;
+        let b = ((data & 0x0000_FF00) >> 8) as u8;
+        let a = (data & 0x0000_00FF) as u8;
+        (r, g, b, a)
+    }
+}
+
+impl PackedOrder for Zrgb {
+    fn pack(r: u8, g: u8, b: u8, _a: u8) -> u32 {
+        ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+    }
+
+    fn unpack(data: u32) -> (u8, u8, u8, u8) {
+        let r = ((data & 0x00FF_0000) >> 16) as u8;
+        let g = ((data & 0x0000_FF00) >> 8) as u8;
+        let b = (data & 0x0000_00FF) as u8;
+        (r, g, b, 255)
+    }
+}
+
+/// A compact 4-byte color backed by a single `u32`, with the channel layout
+/// selected by the `O: PackedOrder` marker type (e.g. `PackedColor<Rgba>`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PackedColor<O> {
+    data: u32,
+    _order: std::marker::PhantomData<O>,
+}
+
+impl<O: PackedOrder> PackedColor<O> {
+    pub fn new(data: u32) -> PackedColor<O> {
+        PackedColor {
+            data,
+            _order: std::marker::PhantomData,
+        }
+    }
+
+    pub fn to_u32(&self) -> u32 {
+        self.data
+    }
+}
+
+impl<O: PackedOrder> From<RgbaColorType> for PackedColor<O> {
+    fn from(c: RgbaColorType) -> PackedColor<O> {
+        let [r, g, b]: [u8; 3] = c.into();
+        let a = (c.a * 255.0).round() as u8;
+        PackedColor::new(O::pack(r, g, b, a))
+    }
+}
+
+impl<O: PackedOrder> From<PackedColor<O>> for RgbaColorType {
+    fn from(c: PackedColor<O>) -> RgbaColorType {
+        let (r, g, b, a) = O::unpack(c.data);
+        let mut color = RgbaColorType::from([r, g, b]);
+        color.a = a as f64 / 255.0;
+        color
+    }
+}
+
+/// Harmony palettes derived from a single base color by rotating hue (and,
+/// for `monochromatic`, lightness) in HSL space.
+pub mod schemes {
+    use super::*;
+
+    pub fn analogous(base: &RgbaColorType) -> Vec<RgbaColorType> {
+        vec![base.rotate_hue(-30.0), *base, base.rotate_hue(30.0)]
+    }
+
+    pub fn triadic(base: &RgbaColorType) -> Vec<RgbaColorType> {
+        vec![base.rotate_hue(-120.0), *base, base.rotate_hue(120.0)]
+    }
+
+    pub fn tetradic(base: &RgbaColorType) -> Vec<RgbaColorType> {
+        vec![
+            *base,
+            base.rotate_hue(90.0),
+            base.rotate_hue(180.0),
+            base.rotate_hue(270.0),
+        ]
+    }
+
+    pub fn split_complementary(base: &RgbaColorType) -> Vec<RgbaColorType> {
+        vec![*base, base.rotate_hue(150.0), base.rotate_hue(210.0)]
+    }
+
+    pub fn monochromatic(base: &RgbaColorType, n: usize) -> Vec<RgbaColorType> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let hsl: HslaColorType = (*base).into();
+        (0..n)
+            .map(|i| {
+                let mut variant = hsl;
+                variant.l = (i + 1) as f64 * 100.0 / (n + 1) as f64;
+                variant.validate();
+                variant.into()
+            })
+            .collect()
+    }
+}
+
 mod tests {
     use super::*;
     #[test]
@@ -275,4 +887,332 @@ mod tests {
         let mut e = c.r - d.r + c.g - d.g + c.b - d.b + c.a - d.a;
         e / 4.0
     }
+
+    #[test]
+    fn test_from_str_hex6() {
+        let c: RgbaColorType = "#FF8000".parse().unwrap();
+        assert_eq!(
+            c,
+            RgbaColorType {
+                r: 1.0,
+                g: 128.0 / 255.0,
+                b: 0.0,
+                a: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_str_hex3() {
+        let c: RgbaColorType = "#f80".parse().unwrap();
+        assert_eq!(
+            c,
+            RgbaColorType {
+                r: 1.0,
+                g: 136.0 / 255.0,
+                b: 0.0,
+                a: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_str_hex8() {
+        let c: RgbaColorType = "#FF800080".parse().unwrap();
+        assert_eq!(c.r, 1.0);
+        assert_eq!(c.a, 128.0 / 255.0);
+    }
+
+    #[test]
+    fn test_from_str_rgb() {
+        let c: RgbaColorType = "rgb(255, 128, 0)".parse().unwrap();
+        assert_eq!(c.a, 1.0);
+        assert_eq!(c.r, 1.0);
+    }
+
+    #[test]
+    fn test_from_str_rgba() {
+        let c: RgbaColorType = "rgba(255, 128, 0, 0.5)".parse().unwrap();
+        assert_eq!(c.a, 0.5);
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let result: Result<RgbaColorType, ParseColorError> = "not-a-color".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_str_malformed_parens_does_not_panic() {
+        let result: Result<RgbaColorType, ParseColorError> = "rgb)(".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_str_malformed_multibyte_hex_does_not_panic() {
+        let result: Result<RgbaColorType, ParseColorError> = "#aébbb".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_hex() {
+        let c = RgbaColorType::new(1.0, 128.0 / 255.0, 0.0);
+        assert_eq!(c.to_hex(), "#FF8000FF");
+    }
+
+    #[test]
+    fn test_packed_color_rgba_round_trip() {
+        let c = RgbaColorType::new(1.0, 128.0 / 255.0, 0.0);
+        let packed: PackedColor<Rgba> = c.into();
+        assert_eq!(packed.to_u32(), 0xFF8000FF);
+        let back: RgbaColorType = packed.into();
+        assert!(error(c, back).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_packed_color_zrgb() {
+        let c = RgbaColorType::new(1.0, 128.0 / 255.0, 0.0);
+        let packed: PackedColor<Zrgb> = c.into();
+        assert_eq!(packed.to_u32(), 0x00FF8000);
+    }
+
+    #[test]
+    fn test_lighten() {
+        let c = RgbaColorType::new(0.5, 0.5, 0.5);
+        let lighter = c.lighten(0.5);
+        let hsl: HslaColorType = c.into();
+        let lighter_hsl: HslaColorType = lighter.into();
+        assert!(lighter_hsl.l > hsl.l);
+    }
+
+    #[test]
+    fn test_darken() {
+        let c = RgbaColorType::new(0.5, 0.5, 0.5);
+        let darker = c.darken(0.5);
+        let hsl: HslaColorType = c.into();
+        let darker_hsl: HslaColorType = darker.into();
+        assert!(darker_hsl.l < hsl.l);
+    }
+
+    #[test]
+    fn test_saturate_and_desaturate() {
+        let c = RgbaColorType::new(0.8, 0.2, 0.2);
+        let more = c.saturate(0.5);
+        let less = c.desaturate(0.5);
+        let hsl: HslaColorType = c.into();
+        let more_hsl: HslaColorType = more.into();
+        let less_hsl: HslaColorType = less.into();
+        assert!(more_hsl.s > hsl.s);
+        assert!(less_hsl.s < hsl.s);
+    }
+
+    #[test]
+    fn test_rotate_hue_wraps() {
+        let c = RgbaColorType::new(1.0, 0.0, 0.0);
+        let rotated = c.rotate_hue(720.0 + 10.0);
+        let hsl: HslaColorType = c.into();
+        let rotated_hsl: HslaColorType = rotated.into();
+        assert!((rotated_hsl.h - (hsl.h + 10.0) % 360.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_lerp() {
+        let a = RgbaColorType::new(0.0, 0.0, 0.0);
+        let b = RgbaColorType::new(1.0, 1.0, 1.0);
+        let mid = a.lerp(&b, 0.5);
+        assert_eq!(mid, RgbaColorType::new(0.5, 0.5, 0.5));
+        assert_eq!(a.lerp(&b, -1.0), a);
+        assert_eq!(a.lerp(&b, 2.0), b);
+    }
+
+    #[test]
+    fn test_gradient_sample() {
+        let black = RgbaColorType::new(0.0, 0.0, 0.0);
+        let white = RgbaColorType::new(1.0, 1.0, 1.0);
+        let gradient = Gradient::new(vec![(0.0, black), (1.0, white)]);
+        assert_eq!(gradient.sample(0.0), Some(black));
+        assert_eq!(gradient.sample(1.0), Some(white));
+        assert_eq!(
+            gradient.sample(0.5),
+            Some(RgbaColorType::new(0.5, 0.5, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_gradient_sample_empty_returns_none() {
+        let gradient = Gradient::new(vec![]);
+        assert_eq!(gradient.sample(0.5), None);
+        assert_eq!(gradient.take(3).collect::<Vec<_>>(), vec![]);
+    }
+
+    #[test]
+    fn test_gradient_new_does_not_panic_on_nan_stop() {
+        let black = RgbaColorType::new(0.0, 0.0, 0.0);
+        let white = RgbaColorType::new(1.0, 1.0, 1.0);
+        let gradient = Gradient::new(vec![(f64::NAN, black), (0.0, white)]);
+        assert!(gradient.sample(0.0).is_some());
+    }
+
+    #[test]
+    fn test_gradient_take() {
+        let black = RgbaColorType::new(0.0, 0.0, 0.0);
+        let white = RgbaColorType::new(1.0, 1.0, 1.0);
+        let gradient = Gradient::new(vec![(0.0, black), (1.0, white)]);
+        let colors: Vec<RgbaColorType> = gradient.take(3).collect();
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[0], black);
+        assert_eq!(colors[2], white);
+    }
+
+    #[test]
+    fn test_rgb_to_xyz_white() {
+        let white = RgbaColorType::new(1.0, 1.0, 1.0);
+        let xyz: XyzColorType = white.into();
+        assert!((xyz.x - 95.047).abs() < 0.05);
+        assert!((xyz.y - 100.0).abs() < 0.05);
+        assert!((xyz.z - 108.883).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_rgb_lab_round_trip() {
+        let c = RgbaColorType::new(0.2, 0.6, 0.8);
+        let lab: LabColorType = c.into();
+        let back: RgbaColorType = lab.into();
+        assert!((c.r - back.r).abs() < 0.001);
+        assert!((c.g - back.g).abs() < 0.001);
+        assert!((c.b - back.b).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_delta_e76() {
+        let a: LabColorType = RgbaColorType::new(1.0, 0.0, 0.0).into();
+        let b: LabColorType = RgbaColorType::new(1.0, 0.0, 0.0).into();
+        assert_eq!(a.delta_e76(&b), 0.0);
+
+        let c: LabColorType = RgbaColorType::new(0.0, 1.0, 0.0).into();
+        assert!(a.delta_e76(&c) > 0.0);
+    }
+
+    #[test]
+    fn test_schemes_analogous() {
+        let base = RgbaColorType::new(1.0, 0.0, 0.0);
+        let colors = schemes::analogous(&base);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[1], base);
+    }
+
+    #[test]
+    fn test_schemes_triadic() {
+        let base = RgbaColorType::new(1.0, 0.0, 0.0);
+        let colors = schemes::triadic(&base);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[1], base);
+    }
+
+    #[test]
+    fn test_schemes_tetradic() {
+        let base = RgbaColorType::new(1.0, 0.0, 0.0);
+        let colors = schemes::tetradic(&base);
+        assert_eq!(colors.len(), 4);
+        assert_eq!(colors[0], base);
+    }
+
+    #[test]
+    fn test_schemes_split_complementary() {
+        let base = RgbaColorType::new(1.0, 0.0, 0.0);
+        let colors = schemes::split_complementary(&base);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors[0], base);
+    }
+
+    #[test]
+    fn test_schemes_monochromatic() {
+        let base = RgbaColorType::new(1.0, 0.0, 0.0);
+        let colors = schemes::monochromatic(&base, 3);
+        assert_eq!(colors.len(), 3);
+        let lightness: Vec<f64> = colors
+            .iter()
+            .map(|c| {
+                let hsl: HslaColorType = (*c).into();
+                hsl.l
+            })
+            .collect();
+        assert!(lightness[0] < lightness[1]);
+        assert!(lightness[1] < lightness[2]);
+    }
+
+    #[test]
+    fn test_add_clamps() {
+        let a = RgbaColorType::new(0.6, 0.6, 0.6);
+        let b = RgbaColorType::new(0.6, 0.6, 0.6);
+        let sum = a + b;
+        assert_eq!(sum, RgbaColorType::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_sub_clamps() {
+        let a = RgbaColorType::new(0.2, 0.2, 0.2);
+        let b = RgbaColorType::new(0.6, 0.6, 0.6);
+        let diff = a - b;
+        assert_eq!(diff, RgbaColorType::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_mul_scalar() {
+        let c = RgbaColorType::new(0.4, 0.4, 0.4);
+        let half = c * 0.5;
+        assert_eq!(half, RgbaColorType::new(0.2, 0.2, 0.2));
+    }
+
+    #[test]
+    fn test_operators_preserve_alpha() {
+        let a = RgbaColorType::new_with_alpha(0.6, 0.6, 0.6, 0.4);
+        let b = RgbaColorType::new_with_alpha(0.2, 0.2, 0.2, 0.9);
+        assert_eq!((a + b).alpha(), 0.4);
+        assert_eq!((a - b).alpha(), 0.4);
+        assert_eq!((a * 0.5).alpha(), 0.4);
+    }
+
+    #[test]
+    fn test_map() {
+        let c = RgbaColorType::new(0.2, 0.4, 0.6);
+        let doubled = c.map(|v| v * 2.0);
+        assert_eq!(doubled, RgbaColorType::new(0.4, 0.8, 1.0));
+    }
+
+    #[test]
+    fn test_alpha_accessors() {
+        let c = RgbaColorType::new(0.1, 0.2, 0.3).with_alpha(0.5);
+        assert_eq!(c.alpha(), 0.5);
+
+        let d = RgbaColorType::new_with_alpha(0.1, 0.2, 0.3, 0.25);
+        assert_eq!(d.alpha(), 0.25);
+    }
+
+    #[test]
+    fn test_rgb_color_type_round_trip() {
+        let rgb = RgbColorType::new(0.1, 0.2, 0.3);
+        let rgba = rgb.with_alpha(0.5);
+        assert_eq!(rgba.alpha(), 0.5);
+        assert_eq!(rgba.without_alpha(), rgb);
+    }
+
+    #[test]
+    fn test_hsl_round_trip_preserves_alpha() {
+        let c = RgbaColorType::new_with_alpha(0.5, 0.2, 0.2, 0.3);
+        let hsl: HslaColorType = c.into();
+        assert_eq!(hsl.a, 0.3);
+        let back: RgbaColorType = hsl.into();
+        assert_eq!(back.alpha(), 0.3);
+    }
+
+    #[test]
+    fn test_hsl_transforms_preserve_alpha() {
+        let c = RgbaColorType::new_with_alpha(0.5, 0.2, 0.2, 0.3);
+        assert_eq!(c.lighten(0.1).alpha(), 0.3);
+        assert_eq!(c.darken(0.1).alpha(), 0.3);
+        assert_eq!(c.saturate(0.1).alpha(), 0.3);
+        assert_eq!(c.desaturate(0.1).alpha(), 0.3);
+        assert_eq!(c.rotate_hue(45.0).alpha(), 0.3);
+    }
 }